@@ -12,22 +12,56 @@ pub(crate) fn import_from(State { db, .. }: &State, args: Args, _properties: Pro
     let mut args = args.0.into_iter();
     parse_args!(args, path: PathBuf);
 
-    let questions: toml::Questions = {
-        let input = std::fs::read_to_string(&path)
-            .into_diagnostic()
-            .with_context(|| format!("reading `{}`", path.display()))?;
+    let input = std::fs::read_to_string(&path)
+        .into_diagnostic()
+        .with_context(|| format!("reading `{}`", path.display()))?;
 
-        ::toml::from_str(&input).into_diagnostic()?
-    };
+    let questions: toml::Questions =
+        ::toml::from_str(&input).map_err(|error| import_error(&path, &input, error))?;
 
     db.add_questions(questions).into_diagnostic()
 }
 
+/// A TOML parse failure with the offending bytes highlighted in-place.
+#[derive(Debug, thiserror::Error, miette::Diagnostic)]
+#[error("{message}")]
+struct ImportError {
+    message: String,
+    #[source_code]
+    src: miette::NamedSource<String>,
+    #[label("{label}")]
+    span: miette::SourceSpan,
+    label: &'static str,
+}
+
+fn import_error(path: &std::path::Path, input: &str, error: ::toml::de::Error) -> miette::Report {
+    let span = error
+        .span()
+        .map(|range| miette::SourceSpan::from((range.start, range.end - range.start)))
+        .unwrap_or_else(|| (0, input.len()).into());
+
+    let message = missing_field_message(&error, input, span.offset()).unwrap_or_else(|| error.message().to_owned());
+
+    ImportError { message, label: "here", src: miette::NamedSource::new(path.display().to_string(), input.to_owned()), span }
+        .into()
+}
+
+/// Rewrites serde's generic "missing field `X`" error to name the question it belongs to.
+fn missing_field_message(error: &::toml::de::Error, input: &str, offset: usize) -> Option<String> {
+    if !error.message().contains("missing field") {
+        return None;
+    }
+
+    let line = input[..offset].matches('\n').count() + 1;
+    Some(format!("{} in question starting at line {line}", error.message()))
+}
+
 pub(crate) fn questions_list(State { db, .. }: &State, _args: Args, properties: Props) -> Result {
     let has_tags = properties.get_owned("has_tags").unwrap_or_default();
     let no_tags = properties.get_owned("no_tags").unwrap_or_default();
+    let search = properties.get_owned("search").unwrap_or_default();
 
-    let questions = db.find_questions(has_tags, no_tags).into_diagnostic()?;
+    let questions = db.find_questions(has_tags, no_tags, search).into_diagnostic()?;
 
     for toml::Question { id, description, answer, distractors, .. } in questions {
         let id = id.unwrap();
@@ -45,11 +79,12 @@ pub(crate) fn questions_about(State { db, .. }: &State, _args: Args, properties:
 
     let has_tags = properties.get_owned("has_tags").unwrap_or_default();
     let no_tags = properties.get_owned("no_tags").unwrap_or_default();
+    let search = properties.get_owned("search").unwrap_or_default();
 
     let mut table = Table::new();
     let mut rows = Vec::new();
 
-    let questions = db.find_questions(has_tags, no_tags).into_diagnostic()?;
+    let questions = db.find_questions(has_tags, no_tags, search).into_diagnostic()?;
     for toml::Question { id, description, answer, distractors, .. } in questions {
         let distractors = distractors.iter().join("\n");
         rows.push(row![id.unwrap(), description, answer, distractors]);
@@ -72,6 +107,77 @@ pub(crate) fn questions(state: &State, _args: Args, properties: Props) -> Result
     Ok(())
 }
 
+pub(crate) fn quiz(state: &State, _args: Args, properties: Props) -> Result {
+    use rand::seq::SliceRandom as _;
+    use std::io::Write as _;
+
+    let has_tags = properties.get_owned("has_tags").unwrap_or_default();
+    let no_tags = properties.get_owned("no_tags").unwrap_or_default();
+
+    let mut due = Vec::new();
+    for question in state.questions(has_tags, no_tags)? {
+        let review = state.db.review(question.id.unwrap()).into_diagnostic()?;
+        let today = chrono::Local::now().date_naive();
+        if review.due_date <= today {
+            due.push((review.due_date, question));
+        }
+    }
+    due.sort_by_key(|(due_date, _)| *due_date);
+
+    if due.is_empty() {
+        println!("Nothing due for review.");
+        return Ok(());
+    }
+
+    let mut rng = rand::thread_rng();
+    let total = due.len();
+    let mut correct = 0;
+
+    for (_, toml::Question { id, description, answer, distractors, .. }) in due {
+        let id = id.unwrap();
+
+        let mut choices = distractors;
+        choices.push(answer.clone());
+        choices.shuffle(&mut rng);
+
+        println!("{description}");
+        for (index, choice) in choices.iter().enumerate() {
+            println!("  {}) {choice}", index + 1);
+        }
+
+        let started = std::time::Instant::now();
+        print!("Your answer: ");
+        std::io::stdout().flush().into_diagnostic()?;
+
+        let mut input = String::new();
+        std::io::stdin().read_line(&mut input).into_diagnostic()?;
+        let hesitated = started.elapsed() > std::time::Duration::from_secs(15);
+
+        let picked = input.trim().parse::<usize>().ok().and_then(|n| choices.get(n.wrapping_sub(1)));
+        let is_correct = picked.is_some_and(|choice| *choice == answer);
+
+        let grade = match (is_correct, hesitated) {
+            (true, false) => 5,
+            (true, true) => 4,
+            (false, true) => 2,
+            (false, false) => 0,
+        };
+
+        state.db.record_grade(id, grade).into_diagnostic()?;
+
+        if is_correct {
+            correct += 1;
+            println!("Correct!\n");
+        } else {
+            println!("Incorrect. The answer was: {answer}\n");
+        }
+    }
+
+    println!("Scored {correct}/{total}");
+
+    Ok(())
+}
+
 pub(crate) fn export(State { db, config, .. }: &State, args: Args, properties: Props) -> Result {
     use std::io::Write as _;
     use std::iter::zip;
@@ -108,7 +214,7 @@ pub(crate) fn export(State { db, config, .. }: &State, args: Args, properties: P
         syntect::easy::HighlightLines::new(rust_syntax, theme)
     };
 
-    let questions = db.find_questions(has_tags, no_tags).into_diagnostic()?;
+    let questions = db.find_questions(has_tags, no_tags, String::new()).into_diagnostic()?;
     for question in questions {
         for (code, index) in zip(stdx::find_rust_code_blocks(&question.description), 0_usize..) {
             let lines = syntect::util::LinesWithEndings::from(&code)
@@ -127,6 +233,106 @@ pub(crate) fn export(State { db, config, .. }: &State, args: Args, properties: P
     Ok(())
 }
 
+pub(crate) fn validate(State { db, .. }: &State, _args: Args, properties: Props) -> Result {
+    use std::process::Command;
+
+    let has_tags = properties.get_owned("has_tags").unwrap_or_default();
+    let no_tags = properties.get_owned("no_tags").unwrap_or_default();
+
+    let questions = db.find_questions(has_tags, no_tags, String::new()).into_diagnostic()?;
+
+    let mut failed = false;
+    for question in questions {
+        let id = question.id.unwrap();
+        for (code, index) in stdx::find_rust_code_blocks(&question.description).into_iter().zip(0_usize..) {
+            let dir = std::env::temp_dir().join(format!("lt_quiz-validate-{id}-{index}"));
+            std::fs::create_dir_all(&dir).into_diagnostic()?;
+
+            let source_path = dir.join("snippet.rs");
+            std::fs::write(&source_path, &code).into_diagnostic()?;
+
+            let output = Command::new("rustc")
+                .args(["--edition", "2021", "--error-format=json", "--crate-type", "lib", "-o"])
+                .arg(dir.join("snippet.out"))
+                .arg(&source_path)
+                .output()
+                .into_diagnostic()
+                .wrap_err("invoking rustc")?;
+
+            for line in String::from_utf8_lossy(&output.stderr).lines() {
+                let Ok(diagnostic) = serde_json::from_str::<RustcDiagnostic>(line) else { continue };
+
+                // Track failure from the level alone: a truly span-less `error` (rare,
+                // but possible) must still fail the gate even though it has nothing to
+                // annotate.
+                failed |= diagnostic.level == "error";
+
+                // Span-less diagnostics are summaries ("aborting due to N previous
+                // errors"), not annotations on a block — they have nothing to point at.
+                let Some(span) = diagnostic.spans.first() else { continue };
+                let Some(severity) = problem_matcher_severity(&diagnostic.level) else { continue };
+
+                let line = span.line_start;
+                let column = span.column_start;
+                let code = diagnostic.code.as_ref().map_or_else(String::new, |code| format!("[{}] ", code.code));
+                // `file=` is a logical `question-<id>/block-<index>.rs`, not a path in the
+                // repo — the snippet only ever exists in a scratch dir, so there is nothing
+                // in the checkout for CI to anchor the annotation to.
+                let file = format!("question-{id}/block-{index}.rs");
+                let message = escape_workflow_message(&diagnostic.message);
+
+                println!(
+                    "::{severity} file={file},line={line},col={column},title=question {id} block {index}::{code}{message}",
+                );
+            }
+
+            std::fs::remove_dir_all(&dir).ok();
+        }
+    }
+
+    if failed {
+        miette::bail!("one or more embedded code blocks failed to compile");
+    }
+
+    Ok(())
+}
+
+/// Escapes a diagnostic message for a GitHub Actions workflow command, where a raw
+/// newline would truncate the annotation at the first line.
+fn escape_workflow_message(message: &str) -> String {
+    message.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+/// Maps an `rustc --error-format=json` level to a GitHub Actions workflow-command
+/// severity (`error`/`warning`/`notice`); levels it has no annotation for are skipped.
+fn problem_matcher_severity(level: &str) -> Option<&'static str> {
+    match level {
+        "error" => Some("error"),
+        "warning" => Some("warning"),
+        "note" | "help" => Some("notice"),
+        _ => None,
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct RustcDiagnostic {
+    message: String,
+    code: Option<RustcCode>,
+    level: String,
+    spans: Vec<RustcSpan>,
+}
+
+#[derive(serde::Deserialize)]
+struct RustcCode {
+    code: String,
+}
+
+#[derive(serde::Deserialize)]
+struct RustcSpan {
+    line_start: usize,
+    column_start: usize,
+}
+
 pub(crate) fn config(State { config, .. }: &State, _args: Args, _properties: Props) -> Result {
     println!("[{}] Theme: {}", config.theme.kind, *config.theme);
 
@@ -1,18 +1,35 @@
 use lt_quiz_core::traits::Database;
 use miette::IntoDiagnostic as _;
-use rusqlite::{self, params, Connection};
+use rusqlite::{self, params, Connection, OptionalExtension as _};
 use stdx::Result;
 
 use crate::toml;
 
+/// SM-2 scheduling state for a single question.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Review {
+    pub(crate) ef: f64,
+    pub(crate) repetition: u32,
+    pub(crate) interval: u32,
+    pub(crate) due_date: chrono::NaiveDate,
+}
+
+impl Review {
+    fn new() -> Self {
+        Self { ef: 2.5, repetition: 0, interval: 0, due_date: chrono::Local::now().date_naive() }
+    }
+}
+
 pub(crate) struct Sqlite {
     pub(crate) conn: Connection,
+    revision: std::cell::Cell<u64>,
 }
 
 impl Sqlite {
     #[cfg(test)]
     pub(crate) fn memory() -> Self {
-        let sqlite = Sqlite { conn: Connection::open_in_memory().unwrap() };
+        let sqlite =
+            Sqlite { conn: Connection::open_in_memory().unwrap(), revision: Default::default() };
         sqlite.migrations().unwrap();
         sqlite
     }
@@ -45,6 +62,64 @@ impl Database for Sqlite {
             .into_diagnostic()?;
         }
 
+        self.revision.set(self.revision.get() + 1);
+
+        Ok(())
+    }
+
+    fn revision(&self) -> u64 {
+        self.revision.get()
+    }
+
+    fn review(&self, question_id: i64) -> Result<Review> {
+        self.conn
+            .query_row(
+                "SELECT ef, repetition, interval, due_date FROM reviews WHERE question_id = ?",
+                [question_id],
+                |row| {
+                    let due_date: String = row.get(3)?;
+                    Ok(Review {
+                        ef: row.get(0)?,
+                        repetition: row.get(1)?,
+                        interval: row.get(2)?,
+                        due_date: due_date.parse().unwrap(),
+                    })
+                },
+            )
+            .optional()
+            .into_diagnostic()
+            .map(|review| review.unwrap_or_else(Review::new))
+    }
+
+    fn record_grade(&self, question_id: i64, grade: u8) -> Result<()> {
+        let review = self.review(question_id)?;
+        let grade = f64::from(grade.min(5));
+
+        let ef = (review.ef + (0.1 - (5.0 - grade) * (0.08 + (5.0 - grade) * 0.02))).max(1.3);
+        let (repetition, interval) = if grade >= 3.0 {
+            let interval = match review.repetition {
+                0 => 1,
+                1 => 6,
+                _ => (f64::from(review.interval) * ef).round() as u32,
+            };
+            (review.repetition + 1, interval)
+        } else {
+            (0, 1)
+        };
+
+        let due_date = chrono::Local::now().date_naive() + chrono::Duration::days(i64::from(interval));
+
+        self.conn
+            .execute(
+                "INSERT INTO reviews (question_id, ef, repetition, interval, due_date) VALUES (?, ?, ?, ?, ?)
+            ON CONFLICT(question_id) DO UPDATE SET
+                ef = excluded.ef, repetition = excluded.repetition, interval = excluded.interval, due_date = excluded.due_date",
+                params![question_id, ef, repetition, interval, due_date.to_string()],
+            )
+            .into_diagnostic()?;
+
+        // Grading updates scheduling state only, not the question rows `find_questions`
+        // serves — it must not invalidate the `State::questions` memo.
         Ok(())
     }
 
@@ -52,30 +127,57 @@ impl Database for Sqlite {
         &self,
         has_tags: Vec<String>,
         no_tags: Vec<String>,
+        search: String,
     ) -> Result<Vec<toml::Question>> {
         use std::fmt::Write as _;
 
         let conn = &self.conn;
-        let mut query = "SELECT q.id, q.description, q.answer, q.distractors
+        let search = search.trim();
+
+        let mut query = if search.is_empty() {
+            "SELECT q.id, q.description, q.answer, q.distractors
         FROM questions AS q
         INNER JOIN question_tags AS qt ON q.id = qt.question_id
         INNER JOIN tags AS t ON qt.tag_id = t.id\n"
-            .to_owned();
+                .to_owned()
+        } else {
+            "SELECT q.id, q.description, q.answer, q.distractors
+        FROM questions_fts
+        INNER JOIN questions AS q ON q.id = questions_fts.rowid
+        INNER JOIN question_tags AS qt ON q.id = qt.question_id
+        INNER JOIN tags AS t ON qt.tag_id = t.id
+        WHERE questions_fts MATCH ?\n"
+                .to_owned()
+        };
 
         if !has_tags.is_empty() {
-            writeln!(query, "WHERE t.text IN ({})", placeholders(has_tags.len())).unwrap();
+            let keyword = if search.is_empty() { "WHERE" } else { "AND" };
+            writeln!(query, "{keyword} t.text IN ({})", placeholders(has_tags.len())).unwrap();
         }
 
         if !no_tags.is_empty() {
             writeln!(query, "AND t.text NOT IN ({})", placeholders(no_tags.len())).unwrap();
         }
 
+        if !search.is_empty() {
+            writeln!(query, "ORDER BY bm25(questions_fts)").unwrap();
+        }
+
         let mut stmt = conn.prepare(&query).into_diagnostic()?;
 
-        let mut tags = has_tags;
-        tags.extend(no_tags);
+        let mut params = Vec::new();
+        if !search.is_empty() {
+            // Quote each token as its own FTS5 string literal (implicit AND between them)
+            // so stray query syntax in user input (`"`, `*`, `AND`, `NEAR`, a `column:`
+            // filter, ...) is matched literally, while multi-word search still finds
+            // questions where the words aren't adjacent.
+            let terms = search.split_whitespace().map(|term| format!("\"{}\"", term.replace('"', "\"\"")));
+            params.push(itertools::join(terms, " "));
+        }
+        params.extend(has_tags);
+        params.extend(no_tags);
 
-        let rows = stmt.query(rusqlite::params_from_iter(tags)).into_diagnostic()?;
+        let rows = stmt.query(rusqlite::params_from_iter(params)).into_diagnostic()?;
         rows.mapped(question_from_row).collect::<rusqlite::Result<_>>().into_diagnostic()
     }
 
@@ -114,6 +216,66 @@ impl Database for Sqlite {
             )
             .into_diagnostic()?;
 
+        let fts_exists = self
+            .conn
+            .query_row(
+                "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'questions_fts'",
+                [],
+                |_| Ok(()),
+            )
+            .optional()
+            .into_diagnostic()?
+            .is_some();
+
+        self.conn
+            .execute(
+                "CREATE VIRTUAL TABLE IF NOT EXISTS questions_fts USING fts5(
+        description, answer, content='questions', content_rowid='id', tokenize='unicode61'
+    )",
+                [],
+            )
+            .into_diagnostic()?;
+
+        if !fts_exists {
+            // The table starts empty — backfill from any questions a pre-existing
+            // database already holds, otherwise `search` silently finds nothing
+            // until every question is re-imported.
+            self.conn
+                .execute("INSERT INTO questions_fts(questions_fts) VALUES ('rebuild')", [])
+                .into_diagnostic()?;
+        }
+
+        self.conn
+            .execute_batch(
+                "
+    CREATE TRIGGER IF NOT EXISTS questions_ai AFTER INSERT ON questions BEGIN
+        INSERT INTO questions_fts(rowid, description, answer) VALUES (new.id, new.description, new.answer);
+    END;
+    CREATE TRIGGER IF NOT EXISTS questions_ad AFTER DELETE ON questions BEGIN
+        INSERT INTO questions_fts(questions_fts, rowid, description, answer) VALUES('delete', old.id, old.description, old.answer);
+    END;
+    CREATE TRIGGER IF NOT EXISTS questions_au AFTER UPDATE ON questions BEGIN
+        INSERT INTO questions_fts(questions_fts, rowid, description, answer) VALUES('delete', old.id, old.description, old.answer);
+        INSERT INTO questions_fts(rowid, description, answer) VALUES (new.id, new.description, new.answer);
+    END;
+    ",
+            )
+            .into_diagnostic()?;
+
+        self.conn
+            .execute(
+                "CREATE TABLE IF NOT EXISTS reviews (
+        question_id INTEGER PRIMARY KEY,
+        ef REAL NOT NULL,
+        repetition INTEGER NOT NULL,
+        interval INTEGER NOT NULL,
+        due_date TEXT NOT NULL,
+        FOREIGN KEY (question_id) REFERENCES questions(id)
+    )",
+                [],
+            )
+            .into_diagnostic()?;
+
         Ok(())
     }
 }
@@ -147,4 +309,40 @@ mod tests {
         assert_eq!(placeholders(3), "?,?,?");
         assert_eq!(placeholders(5), "?,?,?,?,?");
     }
+
+    #[test]
+    fn record_grade_applies_sm2_schedule() {
+        let sqlite = Sqlite::memory();
+        sqlite
+            .add_question(toml::Question {
+                id: None,
+                description: "d".into(),
+                answer: "a".into(),
+                distractors: vec![],
+                tags: vec![],
+            })
+            .unwrap();
+        let id = sqlite.conn.last_insert_rowid();
+
+        let initial = sqlite.review(id).unwrap();
+        assert_eq!(initial.ef, 2.5);
+        assert_eq!(initial.repetition, 0);
+        assert_eq!(initial.interval, 0);
+
+        sqlite.record_grade(id, 5).unwrap();
+        let first = sqlite.review(id).unwrap();
+        assert_eq!(first.repetition, 1);
+        assert_eq!(first.interval, 1);
+        assert!((first.ef - 2.6).abs() < 1e-9);
+
+        sqlite.record_grade(id, 5).unwrap();
+        let second = sqlite.review(id).unwrap();
+        assert_eq!(second.repetition, 2);
+        assert_eq!(second.interval, 6);
+
+        sqlite.record_grade(id, 2).unwrap();
+        let third = sqlite.review(id).unwrap();
+        assert_eq!(third.repetition, 0);
+        assert_eq!(third.interval, 1);
+    }
 }
@@ -1,13 +1,22 @@
+use std::collections::HashMap;
 use std::rc::Rc;
 
 use lt_quiz_core::ir;
 
 use crate::{db, toml, Result};
 
+/// Memoized `questions` results, keyed on the normalized query and invalidated
+/// whenever the database's revision moves past the one the memo was built at.
+#[derive(Default)]
+pub(crate) struct Memo {
+    revision: u64,
+    questions: HashMap<(Vec<String>, Vec<String>), Vec<toml::Question>>,
+}
+
 pub(crate) struct RawState {
     pub(crate) config: ir::Config,
     pub(crate) db: db::Sqlite,
-    pub(crate) cache: std::cell::RefCell<anymap::AnyMap>,
+    pub(crate) cache: std::cell::RefCell<Memo>,
 }
 
 #[derive(Clone)]
@@ -17,25 +26,35 @@ pub(crate) struct State {
 
 impl State {
     pub(crate) fn new(config: ir::Config, db: db::Sqlite) -> Self {
-        Self { raw: RawState { config, db, cache: anymap::AnyMap::new().into() }.into() }
+        Self { raw: RawState { config, db, cache: Memo::default().into() }.into() }
     }
 
     pub(crate) fn questions(
         &self,
-        has_tags: Vec<String>,
-        no_tags: Vec<String>,
+        mut has_tags: Vec<String>,
+        mut no_tags: Vec<String>,
     ) -> Result<Vec<toml::Question>> {
         use lt_quiz_core::traits::Database as _;
 
+        has_tags.sort();
+        no_tags.sort();
+
+        let revision = self.raw.db.revision();
         let mut cache = self.raw.cache.borrow_mut();
-        match cache.get::<Vec<toml::Question>>() {
-            Some(questions) => Ok(questions.clone()),
-            None => {
-                let questions = self.raw.db.find_questions(has_tags, no_tags)?;
-                cache.insert(questions.clone());
-                Ok(questions)
-            }
+        if cache.revision != revision {
+            cache.revision = revision;
+            cache.questions.clear();
         }
+
+        let key = (has_tags, no_tags);
+        if let Some(questions) = cache.questions.get(&key) {
+            return Ok(questions.clone());
+        }
+
+        let (has_tags, no_tags) = key.clone();
+        let questions = self.raw.db.find_questions(has_tags, no_tags, String::new())?;
+        cache.questions.insert(key, questions.clone());
+        Ok(questions)
     }
 }
 
@@ -46,3 +65,38 @@ impl std::ops::Deref for State {
         &self.raw
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Database as _;
+
+    #[test]
+    fn questions_cache_is_keyed_by_filters() {
+        let db = db::Sqlite::memory();
+        db.add_question(toml::Question {
+            id: None,
+            description: "a".into(),
+            answer: "x".into(),
+            distractors: vec![],
+            tags: vec!["a".into()],
+        })
+        .unwrap();
+        db.add_question(toml::Question {
+            id: None,
+            description: "b".into(),
+            answer: "y".into(),
+            distractors: vec![],
+            tags: vec!["b".into()],
+        })
+        .unwrap();
+
+        let state = State::new(ir::Config::default(), db);
+
+        let a = state.questions(vec!["a".into()], vec![]).unwrap();
+        let b = state.questions(vec!["b".into()], vec![]).unwrap();
+
+        assert_eq!(a.iter().map(|question| &question.description).collect::<Vec<_>>(), ["a"]);
+        assert_eq!(b.iter().map(|question| &question.description).collect::<Vec<_>>(), ["b"]);
+    }
+}